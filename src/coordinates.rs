@@ -0,0 +1,173 @@
+//! # Coordinate systems
+//!
+//! Output types produced by [`propagate`](../fn.propagate.html), and the
+//! conversions from the inertial TEME frame SGP4/SDP4 work in down to an
+//! Earth-fixed position and finally a geographic sub-point.
+
+use gravity::GravityConstants;
+
+/// A position and velocity in the **T**rue **E**quator, **M**ean
+/// **E**quinox inertial frame, as produced directly by SGP4/SDP4.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TEME {
+    /// $X$ component of position (km).
+    pub X: f64,
+
+    /// $Y$ component of position (km).
+    pub Y: f64,
+
+    /// $Z$ component of position (km).
+    pub Z: f64,
+
+    /// $\dot{X}$ component of velocity (km/s).
+    pub X_dot: f64,
+
+    /// $\dot{Y}$ component of velocity (km/s).
+    pub Y_dot: f64,
+
+    /// $\dot{Z}$ component of velocity (km/s).
+    pub Z_dot: f64,
+}
+
+impl TEME {
+    /// Rotate this position from the inertial TEME frame into the
+    /// Earth-fixed ECEF frame by `gmst` (radians), the Greenwich Mean
+    /// Sidereal Time at the moment this position was computed. Pass the
+    /// value [`time::gstime`](../time/fn.gstime.html) returns for the
+    /// corresponding Julian date.
+    pub fn to_ecef(self, gmst: f64) -> Ecef {
+        let (sin_gmst, cos_gmst) = gmst.sin_cos();
+        Ecef {
+            X: self.X * cos_gmst + self.Y * sin_gmst,
+            Y: self.Y * cos_gmst - self.X * sin_gmst,
+            Z: self.Z,
+        }
+    }
+}
+
+/// A position in the Earth-fixed **EC**entric **E**arth-**F**ixed frame,
+/// i.e. the TEME frame rotated to account for Earth's sidereal rotation.
+/// Units are the same as the [`TEME`] position it was derived from (km).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ecef {
+    /// $X$ component (km).
+    pub X: f64,
+
+    /// $Y$ component (km).
+    pub Y: f64,
+
+    /// $Z$ component (km).
+    pub Z: f64,
+}
+
+impl Ecef {
+    /// Convert to geodetic latitude, longitude, and altitude on the
+    /// ellipsoid described by `gc` (i.e. the same [`GravityConstants`]
+    /// used to propagate this position).
+    ///
+    /// Longitude follows directly from `atan2(Y, X)`. Latitude is found by
+    /// Bowring's iterative closed-form solution, which converges to better
+    /// than a micro-radian in only a handful of iterations even for
+    /// highly eccentric ellipsoids; altitude then follows from the
+    /// prime-vertical radius of curvature at the converged latitude.
+    pub fn to_geodetic(self, gc: GravityConstants) -> Geodetic {
+        let e2 = gc.f * (2.0 - gc.f);
+        let r = (self.X * self.X + self.Y * self.Y).sqrt();
+        let lon = self.Y.atan2(self.X);
+
+        let mut lat = self.Z.atan2(r);
+        for _ in 0..10 {
+            let sin_lat = lat.sin();
+            let n = gc.xkmper / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+            let next_lat = (self.Z + n * e2 * sin_lat).atan2(r);
+            let converged = (next_lat - lat).abs() < 1.0e-12;
+            lat = next_lat;
+            if converged {
+                break;
+            }
+        }
+
+        let sin_lat = lat.sin();
+        let n = gc.xkmper / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+        let alt = r / lat.cos() - n;
+
+        Geodetic { lat, lon, alt }
+    }
+}
+
+/// A geographic sub-point: geodetic latitude, longitude, and altitude
+/// above the reference ellipsoid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Geodetic {
+    /// Geodetic latitude (radians).
+    pub lat: f64,
+
+    /// Longitude (radians).
+    pub lon: f64,
+
+    /// Altitude above the ellipsoid (km), in the same units as the
+    /// [`Ecef`] position it was derived from.
+    pub alt: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gravity::GravityModel;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1.0e-6, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn to_ecef_rotates_by_gmst() {
+        let teme = TEME {
+            X: 1000.0,
+            Y: 0.0,
+            Z: 2000.0,
+            X_dot: 0.0,
+            Y_dot: 0.0,
+            Z_dot: 0.0,
+        };
+
+        // At gmst = 0 the TEME and ECEF X axes coincide, so rotation is a
+        // no-op.
+        let ecef0 = teme.to_ecef(0.0);
+        assert_close(ecef0.X, 1000.0);
+        assert_close(ecef0.Y, 0.0);
+        assert_close(ecef0.Z, 2000.0);
+
+        // At gmst = pi/2 the Earth has rotated a quarter turn underneath
+        // the inertial frame, so the ECEF frame's X axis now points along
+        // TEME's Y axis.
+        let ecef1 = teme.to_ecef(::std::f64::consts::FRAC_PI_2);
+        assert_close(ecef1.X, 0.0);
+        assert_close(ecef1.Y, -1000.0);
+        assert_close(ecef1.Z, 2000.0);
+    }
+
+    #[test]
+    fn to_geodetic_round_trip() {
+        let gc = GravityModel::Wgs84.constants();
+
+        // A known geodetic fix: roughly 45 degrees north, 30 degrees east,
+        // 500 km up. Build its ECEF position directly from the ellipsoid
+        // formula and check `to_geodetic` recovers the same fix.
+        let lat = 45.0_f64.to_radians();
+        let lon = 30.0_f64.to_radians();
+        let alt = 500.0;
+
+        let e2 = gc.f * (2.0 - gc.f);
+        let n = gc.xkmper / (1.0 - e2 * lat.sin() * lat.sin()).sqrt();
+        let ecef = Ecef {
+            X: (n + alt) * lat.cos() * lon.cos(),
+            Y: (n + alt) * lat.cos() * lon.sin(),
+            Z: (n * (1.0 - e2) + alt) * lat.sin(),
+        };
+
+        let geodetic = ecef.to_geodetic(gc);
+        assert!((geodetic.lat - lat).abs() < 1.0e-9, "expected {}, got {}", lat, geodetic.lat);
+        assert!((geodetic.lon - lon).abs() < 1.0e-9, "expected {}, got {}", lon, geodetic.lon);
+        assert!((geodetic.alt - alt).abs() < 1.0e-6, "expected {}, got {}", alt, geodetic.alt);
+    }
+}
@@ -0,0 +1,56 @@
+//! # Errors
+//!
+//! Rather than silently returning a position computed from out-of-range or
+//! decayed elements, [`propagate_with`](../fn.propagate_with.html) and
+//! [`propagate`](../fn.propagate.html) report the specific failure so a
+//! caller can distinguish "bad input" from "satellite has re-entered" from
+//! "numerical iteration failed to converge".
+
+use std::error;
+use std::fmt;
+
+/// Errors that can occur while propagating an orbit with SGP4/SDP4. See
+/// [`propagate_with`](../fn.propagate_with.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sgp4Error {
+    /// Eccentricity (of the TLE's mean elements, or of the mean elements
+    /// after propagation to the requested time) left the valid range
+    /// `[0, 1)`.
+    InvalidEccentricity(f64),
+
+    /// The TLE's mean motion (or the semi-major axis derived from it) was
+    /// out of range -- e.g. non-positive.
+    InvalidMeanMotion(f64),
+
+    /// The semi-major axis decayed below one Earth radius: the satellite
+    /// has re-entered and this model no longer applies.
+    Decayed {
+        /// The semi-major axis (Earth radii) at the requested time.
+        am: f64,
+    },
+
+    /// Kepler's equation `E - e sin E = M` failed to converge within the
+    /// iteration budget.
+    KeplerDidNotConverge,
+
+    /// The mean elements describe a pathologically eccentric, grazing
+    /// orbit: either the drag parameter `s` very nearly equals the
+    /// semi-major axis `aₒ"` (so `ξ = 1/(aₒ" - s)` is blowing up), or the
+    /// eccentricity-ratio term `η` has reached 1 (so `(1 - η²)` is about
+    /// to divide through to infinity in the drag/periodics terms).
+    NearSingularOrbit,
+}
+
+impl fmt::Display for Sgp4Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Sgp4Error::InvalidEccentricity(e) => write!(f, "eccentricity {} is outside [0, 1)", e),
+            Sgp4Error::InvalidMeanMotion(n) => write!(f, "mean motion/semi-major axis out of range (n = {})", n),
+            Sgp4Error::Decayed { am } => write!(f, "satellite has decayed (semi-major axis {} Earth radii)", am),
+            Sgp4Error::KeplerDidNotConverge => write!(f, "Kepler's equation did not converge"),
+            Sgp4Error::NearSingularOrbit => write!(f, "orbit is too eccentric/near-singular to propagate (perigee too close to the drag parameter s)"),
+        }
+    }
+}
+
+impl error::Error for Sgp4Error {}
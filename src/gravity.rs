@@ -0,0 +1,125 @@
+//! # Gravity models
+//!
+//! SGP4/SDP4 are defined relative to a particular Earth gravity constant
+//! set. NORAD-produced TLEs are meant to be propagated with WGS72 (the
+//! constants baked into the original operational SGP4), but higher-fidelity
+//! applications may prefer the newer WGS84 ellipsoid, or need to reproduce
+//! older ephemerides generated against the legacy WGS72 constants. See
+//! [`propagate_with`](../fn.propagate_with.html).
+
+/// Which Earth gravity constant set to propagate with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GravityModel {
+    /// The original (1972-vintage) WGS72 constants, with $k_e$ taken as the
+    /// literal published constant rather than derived from $\mu$. Used to
+    /// reproduce ephemerides generated by older operational SGP4 code.
+    Wgs72Old,
+
+    /// WGS72, the constant set almost all NORAD TLEs are intended to be
+    /// propagated with. The default used by [`propagate`](../fn.propagate.html).
+    Wgs72,
+
+    /// WGS84, for applications that want the more recent Earth ellipsoid
+    /// rather than bit-for-bit fidelity with NORAD-produced ephemerides.
+    Wgs84,
+}
+
+/// The gravity-model-dependent constants SGP4/SDP4 are parameterized over.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GravityConstants {
+    /// $k_e = \sqrt{GM_\oplus}$ (Earth radii/minute)^(3/2).
+    pub ke: f64,
+
+    /// $k_2 = \frac{1}{2}J_2aE^2$.
+    pub k2: f64,
+
+    /// $k_4 = -\frac{3}{8}J_4aE^4$.
+    pub k4: f64,
+
+    /// $J_2$  Second-order (oblateness) harmonic gravity coefficient.
+    pub j2: f64,
+
+    /// $J_3$  Third-order harmonic gravity coefficient.
+    pub j3: f64,
+
+    /// $J_4$  Fourth-order harmonic gravity coefficient.
+    pub j4: f64,
+
+    /// Kilometers per Earth radius.
+    pub xkmper: f64,
+
+    /// $a_E$  Equatorial radius of the Earth, in Earth radii (always 1.0;
+    /// kept as a named constant for parity with the reference formulas).
+    pub ae: f64,
+
+    /// $s$  The altitude-dependent drag parameter (Earth radii).
+    pub s: f64,
+
+    /// $(q_0 - s)^4$  The drag denominator constant (Earth radii^4).
+    pub q0ms: f64,
+
+    /// $f$  Ellipsoid flattening, used by
+    /// [`to_geodetic`](../coordinates/struct.Ecef.html#method.to_geodetic)
+    /// to convert ECEF positions to geodetic latitude/longitude/altitude.
+    pub f: f64,
+}
+
+impl GravityModel {
+    /// Derive the constants for this gravity model.
+    pub fn constants(self) -> GravityConstants {
+        match self {
+            GravityModel::Wgs72Old => {
+                let xkmper = 6378.135;
+                GravityConstants {
+                    ke: 7.43669161e-2,
+                    k2: 5.413080e-4,
+                    k4: 0.62098875e-6,
+                    j2: 1.082616e-3,
+                    j3: -0.253881e-5,
+                    j4: -1.65597e-6,
+                    xkmper,
+                    ae: 1.0,
+                    s: 78.0 / xkmper + 1.0,
+                    q0ms: ((120.0 - 78.0) / xkmper).powi(4),
+                    f: 1.0 / 298.26,
+                }
+            }
+            GravityModel::Wgs72 => {
+                let xkmper: f64 = 6378.135;
+                let mu: f64 = 398600.8;
+                GravityConstants {
+                    ke: 60.0 / (xkmper * xkmper * xkmper / mu).sqrt(),
+                    k2: 5.413080e-4,
+                    k4: 0.62098875e-6,
+                    j2: 1.082616e-3,
+                    j3: -0.253881e-5,
+                    j4: -1.65597e-6,
+                    xkmper,
+                    ae: 1.0,
+                    s: 78.0 / xkmper + 1.0,
+                    q0ms: ((120.0 - 78.0) / xkmper).powi(4),
+                    f: 1.0 / 298.26,
+                }
+            }
+            GravityModel::Wgs84 => {
+                let xkmper: f64 = 6378.137;
+                let mu: f64 = 398600.5;
+                let j2 = 1.08262998905e-3;
+                let j4 = -1.61098761e-6;
+                GravityConstants {
+                    ke: 60.0 / (xkmper * xkmper * xkmper / mu).sqrt(),
+                    k2: 0.5 * j2,
+                    k4: -0.375 * j4,
+                    j2,
+                    j3: -0.253215306e-5,
+                    j4,
+                    xkmper,
+                    ae: 1.0,
+                    s: 78.0 / xkmper + 1.0,
+                    q0ms: ((120.0 - 78.0) / xkmper).powi(4),
+                    f: 1.0 / 298.257223563,
+                }
+            }
+        }
+    }
+}
@@ -30,47 +30,47 @@ Original paper: [Hoots_Roehrich_1980_SPACETRACK_REPORT_NO_3.pdf](../Hoots_Roehri
 
 pub mod tle;
 pub mod coordinates;
+pub mod error;
+pub mod gravity;
+pub mod time;
 
-use std::io::Write;
+use error::Sgp4Error;
+use gravity::{GravityConstants, GravityModel};
+use time::OperationMode;
 
-
-macro_rules! println_stderr(
-    ($($arg:tt)*) => { {
-        let r = writeln!(&mut ::std::io::stderr(), $($arg)*);
-        r.expect("failed printing to stderr");
-    } }
-);
-
-
-/// $k_e = 7.43669161 \times 10\^{-2}$  Orbital constant for Earth defined as $\sqrt{GM_{\oplus}}$ where $G$ is Newton’s universal gravitational constant and $M_{\oplus}$ is the mass of the Earth. Units: $(\frac{\mathrm{Earth\ radii}}{\mathrm{minute}})\^{\frac{3}{2}}$
-pub const ke: f64 = 7.43669161e-2;
-
-/// $k_2 = 5.413080 \times 10\^{-4}$  Harmonic gravity constant for the SGP4 model. Defined as $\frac{1}{2}J_2aE\^2$.
-pub const k2: f64 = 5.413080e-4;
-
-/// $R_\oplus = 1.0$  Radius of the Earth (in Earth Radii).
-pub const RE: f64 = 1.0;
-
-/// $6378.135$ kilometers/Earth radii.
-pub const XKMPER: f64 = 6378.135;
-
-/// S (?)
-pub const S: f64 = 1.01222928;
-
-/// qs4 (?)
-pub const qs4: f64 = 1.88027916e-9;
+use std::f64::consts::PI;
 
 
 /// ## Propagate
 ///
-/// Propagate the orbit to the desired time.
-pub fn propagate(tle: tle::TLE, time: f64) -> coordinates::TEME {
+/// Propagate the orbit to the desired time, using the WGS72 gravity
+/// constants that NORAD-produced TLEs are meant to be propagated with.
+/// To select a different gravity model (e.g. to reproduce an ephemeris
+/// generated with the legacy WGS72OLD constants, or to use WGS84), see
+/// [`propagate_with`].
+pub fn propagate(tle: tle::TLE, time: f64) -> Result<coordinates::TEME, Sgp4Error> {
+    propagate_with(tle, time, GravityModel::Wgs72, OperationMode::Afspc)
+}
+
+/// Propagate the orbit to the desired time using the constants from the
+/// given [`GravityModel`] and the Greenwich-sidereal-time/eccentricity-guard
+/// convention of the given [`OperationMode`].
+pub fn propagate_with(tle: tle::TLE, time: f64, model: GravityModel, mode: OperationMode) -> Result<coordinates::TEME, Sgp4Error> {
+    let gc = model.constants();
+    let eps = mode.eccentricity_guard();
 
     // Copy from NORAD elements
     let n0 = tle.mean_motion;
     let i0 = tle.i;
     let e0 = tle.e;
 
+    if n0 <= 0.0 {
+        return Err(Sgp4Error::InvalidMeanMotion(n0));
+    }
+    if !(0.0..1.0).contains(&e0) {
+        return Err(Sgp4Error::InvalidEccentricity(e0));
+    }
+
     // Pre-compute expensive things
     let cos_i0 = i0.cos();
     let cos2_i0 = cos_i0 * cos_i0;
@@ -87,12 +87,12 @@ pub fn propagate(tle: tle::TLE, time: f64) -> coordinates::TEME {
     //       kₑ  ⅔
     // a₁ = ----
     //       nₒ
-    let a1 = (ke/n0).powf(2.0/3.0);
+    let a1 = (gc.ke/n0).powf(2.0/3.0);
 
     //      3 k₂   (3 cos² iₒ - 1)
     // δ₁ = - --- ----------------
     //      2 a₁²   (1 - eₒ²)³/₂
-    let d1 = (3.0 * k2  * ( 3.0 * cos2_i0 - 1.0)) / (2.0 * a1 * a1 * ( 1.0 - e02).powf(3.0/2.0));
+    let d1 = (3.0 * gc.k2  * ( 3.0 * cos2_i0 - 1.0)) / (2.0 * a1 * a1 * ( 1.0 - e02).powf(3.0/2.0));
 
     //         ⌈     1           134    ⌉
     // aₒ = a₁ | 1 - -δ₁ - δ₁² - ---δ₁³ |
@@ -102,7 +102,7 @@ pub fn propagate(tle: tle::TLE, time: f64) -> coordinates::TEME {
     //      3 k₂   (3 cos² iₒ - 1)
     // δₒ = - --- ----------------
     //      2 aₒ²   (1 - eₒ²)³/₂
-    let d0 = (3.0 * k2  * ( 3.0 * cos2_i0 - 1.0)) / (2.0 * a0 * a0 * ( 1.0 - e02).powf(3.0/2.0));
+    let d0 = (3.0 * gc.k2  * ( 3.0 * cos2_i0 - 1.0)) / (2.0 * a0 * a0 * ( 1.0 - e02).powf(3.0/2.0));
 
     //          nₒ
     // nₒ" = --------
@@ -114,36 +114,42 @@ pub fn propagate(tle: tle::TLE, time: f64) -> coordinates::TEME {
     //       (1 - δₒ)
     let ao_dp = a0 / (1.0 - d0);
 
+    // Satellites with a period of 225 minutes or more are perturbed
+    // strongly enough by the Moon and Sun (and, near the 1:1 and 2:1
+    // resonances, by the Earth's gravitational field) that the near-Earth
+    // model below isn't sufficient; those get the deep-space (SDP4)
+    // corrections applied via `sdp4_corrections` instead.
+    let period = 2.0 * PI / n0_dp;
+    let deep_space = period >= 225.0;
+
 
     // ************************************************************************
     // Section 2.
     // Determine apogee and perigee so we can deicide which SGP4 variant to
     // use later.
 
-    // p = [aₒ"(1 - eₒ) - Rₑ] * XKMPER
-    let perigee = (ao_dp * (1.0 - e0) - RE) * XKMPER;
+    // p = [aₒ"(1 - eₒ) - Rₑ] * gc.xkmper
+    let perigee = (ao_dp * (1.0 - e0) - gc.ae) * gc.xkmper;
 
-    // p = [aₒ"(1 + eₒ) - Rₑ] * XKMPER
-    let apogee = (ao_dp * (1.0 + e0) - RE) * XKMPER;
+    // p = [aₒ"(1 + eₒ) - Rₑ] * gc.xkmper
+    let _apogee = (ao_dp * (1.0 + e0) - gc.ae) * gc.xkmper;
 
 
     // ************************************************************************
     // Section 3.
     // Calculate more constants
 
-    // Set parameter "s" depending on perigee of the satellite:
-    let s: f64;
-    if perigee < 156.0 {
-        // s = aₒ"(1 − eₒ) − s + aE
-        s = ao_dp * (1.0 - e0) - S + RE;
-    }
-    else if perigee < 98.0 {
-        s = (20.0 / XKMPER) + RE;
-    }
-    else {
-        // For everything else use original value of s
-        s = S;
-    }
+    // Set parameter "s" and its companion drag constant (qₒ-s)⁴ depending
+    // on the perigee of the satellite. Below 156 km both are recomputed
+    // from a perigee-derived s₄ (clamped to a 20 km floor below 98 km
+    // perigee); above that, the gravity model's defaults apply unchanged.
+    let (s, qoms2t) = if perigee < 156.0 {
+        let s4 = if perigee < 98.0 { 20.0 } else { perigee - 78.0 };
+        let qoms2t = ((120.0 - s4) / gc.xkmper).powi(4);
+        (s4 / gc.xkmper + gc.ae, qoms2t)
+    } else {
+        (gc.s, gc.q0ms)
+    };
 
     // θ = cos iₒ
     let O = cos_i0;
@@ -152,6 +158,12 @@ pub fn propagate(tle: tle::TLE, time: f64) -> coordinates::TEME {
     //        1
     // ξ = -------
     //     aₒ" - s
+    //
+    // A grazing orbit whose perigee has dragged `s` up to nearly aₒ" would
+    // blow this up to infinity instead of producing a clean error.
+    if (ao_dp - s).abs() < 1.0e-6 {
+        return Err(Sgp4Error::NearSingularOrbit);
+    }
     let xi = 1.0 / (ao_dp - s);
     let xi4 = xi.powi(4);
 
@@ -165,28 +177,906 @@ pub fn propagate(tle: tle::TLE, time: f64) -> coordinates::TEME {
     let n3 = n.powi(3);
     let n4 = n.powi(4);
 
+    // (1 - η²) is about to be raised to a negative power below; a
+    // pathologically eccentric orbit can push η to (or past) 1 and blow
+    // that up to infinity/NaN instead of a clean error.
+    if n2 >= 1.0 - 1.0e-6 {
+        return Err(Sgp4Error::NearSingularOrbit);
+    }
+
     //                           -⁷/₂⌈   ⌈    3                ⌉   3   k₂ξ    ⌈ 1   3  ⌉                ⌉
     // C₂ = (qₒ − s)⁴ξ⁴nₒ"(1 - η²)   |aₒ"|1 + -η² + 4eₒη + eₒη³| + - -------- |-- + -θ²|(8 + 24η² + 3η⁴)|
     //                               ⌊   ⌊    2                ⌋   2 (1 - η²) ⌊ 2   2  ⌋                ⌋
-    let C2 = qs4 * xi4 * n0_dp * (1.0 - n2).powf(-7.0/2.0) * (ao_dp * (1.0 + (1.5 * n2) + (4.0 * e0 * n) + (e0 * n3)) + 1.5 * (k2 * xi)/(1.0 - n2) * (-0.5 + (1.5 * O2)) * (8.0 + (24.0 * n2) + (3.0 * n4)));
+    let C2 = qoms2t * xi4 * n0_dp * (1.0 - n2).powf(-7.0/2.0) * (ao_dp * (1.0 + (1.5 * n2) + (4.0 * e0 * n) + (e0 * n3)) + 1.5 * (gc.k2 * xi)/(1.0 - n2) * (-0.5 + (1.5 * O2)) * (8.0 + (24.0 * n2) + (3.0 * n4)));
+
+    // η is also used below under the more conventional name "eta" so the
+    // drag/periodics code reads closer to the reference equations.
+    let eta = n;
+    let eta2 = n2;
+    let eta3 = n3;
+
+    // C₁ = B*C₂
+    let C1 = tle.bstar * C2;
+
+    // -A₃/k₂, the (positive) ratio of the third zonal harmonic to k₂.
+    let a3ovk2 = -gc.j3 / gc.k2;
+
+    let sin_i0 = i0.sin();
+
+    //      qₒ₋ₛ⁴ξ⁵(-A₃/k₂)nₒ"aEsin iₒ
+    // C₃ = ---------------------------   (only defined for eₒ above the
+    //                 eₒ                  near-circular guard)
+    let C3 = if e0 > eps {
+        qoms2t * xi4 * xi * a3ovk2 * n0_dp * gc.ae * sin_i0 / e0
+    } else {
+        0.0
+    };
+
+    let x3thm1 = 3.0 * O2 - 1.0;
+    let x1mth2 = 1.0 - O2;
+    let x7thm1 = 7.0 * O2 - 1.0;
+
+    let xi3 = xi.powi(3);
+
+    // C₄, the drag term feeding into eccentricity, mean anomaly and
+    // argument of perigee secular rates.
+    let C4 = 2.0 * n0_dp * qoms2t * xi3 * ao_dp * B * B *
+        ((eta * (2.0 + 0.5 * eta2) + e0 * (0.5 + 2.0 * eta2))
+         - (2.0 * gc.k2 * xi) / (ao_dp * (1.0 - eta2)) *
+           (-3.0 * x3thm1 * (1.0 - 2.0 * e0 * eta + eta2 * (1.5 - 0.5 * e0 * eta))
+            + 0.75 * x1mth2 * (2.0 * eta2 - e0 * eta - e0 * eta3) * (2.0 * tle.argp).cos()));
+
+    // C₅, the drag term feeding into the mean anomaly correction.
+    let C5 = 2.0 * qoms2t * xi3 * ao_dp * B * B * (1.0 + 2.75 * eta * (eta + e0) + e0 * eta3);
+
+    // Below 220 km perigee we use the simplified-drag model: the D2-D4
+    // and higher-order C-term contributions are dropped entirely.
+    let isimp = perigee < 220.0;
+
+    let (D2, D3, D4) = if isimp {
+        (0.0, 0.0, 0.0)
+    } else {
+        let d2 = 4.0 * ao_dp * xi * C1 * C1;
+        let temp = d2 * xi * C1 / 3.0;
+        let d3 = (17.0 * ao_dp + s) * temp;
+        let d4 = 0.5 * temp * ao_dp * xi * (221.0 * ao_dp + 31.0 * s) * C1;
+        (d2, d3, d4)
+    };
+
+    let t2cof = 1.5 * C1;
+    let t3cof = D2 + 2.0 * C1 * C1;
+    let t4cof = 0.25 * (3.0 * D3 + C1 * (12.0 * D2 + 10.0 * C1 * C1));
+    let t5cof = 0.2 * (3.0 * D4 + 12.0 * C1 * D3 + 6.0 * D2 * D2 + 15.0 * C1 * C1 * (2.0 * D2 + C1 * C1));
+
+
+    // ************************************************************************
+    // Section 4.
+    // Secular rates of the mean anomaly, argument of perigee and node, and
+    // the constants needed for the drag-induced mean anomaly correction
+    // and long/short-period periodics.
+
+    let pinvsq = 1.0 / (ao_dp * ao_dp * B * B * B * B);
+    let temp1 = 1.5 * gc.k2 * pinvsq * n0_dp;
+    let temp2 = 0.5 * temp1 * gc.k2 * pinvsq;
+    let temp3 = 1.25 * gc.k4 * pinvsq * pinvsq * n0_dp;
+
+    // Mdot, the secular rate of change of mean anomaly.
+    let mdot = n0_dp + 0.5 * temp1 * B * x3thm1 + 0.0625 * temp2 * B * (13.0 - 78.0 * O2 + 137.0 * O2 * O2);
+
+    // ωdot, the secular rate of change of the argument of perigee.
+    let argpdot = -0.5 * temp1 * x1mth2 + 0.0625 * temp2 * (7.0 - 114.0 * O2 + 395.0 * O2 * O2) + temp3 * (3.0 - 36.0 * O2 + 49.0 * O2 * O2);
+
+    // Ωdot, the secular rate of change of the right ascension of the node.
+    let nodedot = (-temp1 * O) + ((0.5 * temp2 * (4.0 - 19.0 * O2)) + (2.0 * temp3 * (3.0 - 7.0 * O2))) * O;
+
+    let omgcof = tle.bstar * C3 * tle.argp.cos();
+
+    let xmcof = if e0 > eps {
+        -(2.0 / 3.0) * qoms2t * xi4 * tle.bstar * gc.ae / eta
+    } else {
+        0.0
+    };
+
+    let nodecf = 3.5 * B * B * (-temp1 * O) * C1;
+
+    // Constants for the long-period periodics.
+    let aycof = 0.25 * a3ovk2 * sin_i0;
+    let denom = if (1.0 + O).abs() < 1.5e-12 { 1.5e-12_f64.copysign(1.0 + O) } else { 1.0 + O };
+    let xlcof = 0.125 * a3ovk2 * sin_i0 * (3.0 + 5.0 * O) / denom;
+
+    let delmo = (1.0 + eta * tle.M.cos()).powi(3);
+
+
+    // ************************************************************************
+    // Section 5.
+    // Update mean elements to the desired time `time` (minutes since epoch).
+
+    let t = time;
+
+    let xmdf = tle.M + mdot * t;
+    let argpdf = tle.argp + argpdot * t;
+    let nodedf = tle.raan + nodedot * t;
+
+    let mut tempa = 1.0 - C1 * t;
+    let mut tempe = tle.bstar * C4 * t;
+    let mut templ = t2cof * t * t;
+
+    let (mp, argpp) = if !isimp {
+        let delomg = omgcof * t;
+        let delmtemp = 1.0 + eta * xmdf.cos();
+        let delm = xmcof * (delmtemp * delmtemp * delmtemp - delmo);
+        let temp = delomg + delm;
+
+        tempa -= D2 * t * t + D3 * t * t * t + D4 * t * t * t * t;
+        tempe += tle.bstar * C5 * (xmdf.sin() - tle.M.sin());
+        templ += t3cof * t * t * t + t * t * t * t * (t4cof + t * t5cof);
+
+        (xmdf + temp, argpdf - temp)
+    } else {
+        (xmdf, argpdf)
+    };
+
+    let am = ao_dp * tempa * tempa;
+    let em = e0 - tempe;
+    let nodep = nodedf + nodecf * t * t;
+
+    if am < 1.0 {
+        return Err(Sgp4Error::Decayed { am });
+    }
+
+    let _beta = (1.0 - em * em).sqrt();
+    let _np = gc.ke / am.powf(1.5);
+
+    // Deep-space satellites get an additional lunar/solar (and, near
+    // resonance, Earth-resonant) correction to the mean elements here;
+    // everything from this point on is shared between SGP4 and SDP4.
+    let elements = MeanElements { em, mp, argpp, nodep, incl: tle.i };
+    let elements = if deep_space {
+        let ctx = DeepSpaceContext { gc, mode, tle: &tle, i0, e0, n0_dp, argpdot };
+        sdp4_corrections(&ctx, t, elements)
+    } else {
+        elements
+    };
+
+    if !(0.0..1.0).contains(&elements.em) {
+        return Err(Sgp4Error::InvalidEccentricity(elements.em));
+    }
+
+    let coef = PeriodicsCoef { x3thm1, x1mth2, x7thm1, aycof, xlcof, cosio: O, sinio: sin_i0 };
+    periodics(gc, n0_dp, templ, elements, am, coef)
+}
+
+/// The mean elements threaded between the drag/secular corrections,
+/// [`sdp4_corrections`], and [`periodics`] — bundled together so this
+/// per-call state doesn't have to be threaded positionally through half a
+/// dozen functions.
+#[derive(Debug, Clone, Copy)]
+struct MeanElements {
+    em: f64,
+    mp: f64,
+    argpp: f64,
+    nodep: f64,
+    incl: f64,
+}
+
+/// The gravity/operation-mode configuration and TLE mean elements at
+/// epoch, shared by [`deep_space_init`] and [`sdp4_corrections`].
+struct DeepSpaceContext<'a> {
+    gc: GravityConstants,
+    mode: OperationMode,
+    tle: &'a tle::TLE,
+    i0: f64,
+    e0: f64,
+    n0_dp: f64,
+    /// Secular rate of change of the argument of perigee (Section 4),
+    /// needed by the 12-hour resonance to track `xomi` between steps.
+    argpdot: f64,
+}
+
+/// The inclination-dependent coefficients from Sections 3-4, constant
+/// over time, that [`periodics`] needs for the short-period corrections.
+#[derive(Debug, Clone, Copy)]
+struct PeriodicsCoef {
+    x3thm1: f64,
+    x1mth2: f64,
+    x7thm1: f64,
+    aycof: f64,
+    xlcof: f64,
+    cosio: f64,
+    sinio: f64,
+}
+
+
+/// ## Section 6 & 7.
+///
+/// Long-period periodics, Kepler's equation, short-period periodics, and
+/// the rotation into TEME. Identical for SGP4 and SDP4 — the only
+/// difference between the two models is in the mean elements fed in here.
+fn periodics(gc: GravityConstants, n0_dp: f64, templ: f64, elements: MeanElements, am: f64,
+             coef: PeriodicsCoef) -> Result<coordinates::TEME, Sgp4Error> {
+    let MeanElements { em, mp, argpp, nodep, incl } = elements;
+    let PeriodicsCoef { x3thm1, x1mth2, x7thm1, aycof, xlcof, cosio, sinio } = coef;
+
+    let axnl = em * argpp.cos();
+    let temp = 1.0 / (am * (1.0 - em * em));
+    let aynl = em * argpp.sin() + temp * aycof;
+    let xl = mp + argpp + nodep + n0_dp * templ + temp * xlcof * axnl;
+
+    // Solve Kepler's equation E − e sin E = M (here expressed in terms of
+    // axnl, aynl) for the eccentric longitude `eo1` by Newton's method.
+    let u = (xl - nodep) % (2.0 * PI);
+    let mut eo1 = u;
+    let mut converged = false;
+    for _ in 0..10 {
+        let sineo1 = eo1.sin();
+        let coseo1 = eo1.cos();
+        let delta = (u - aynl * coseo1 + axnl * sineo1 - eo1) / (1.0 - coseo1 * axnl - sineo1 * aynl);
+        let delta = if delta.abs() > 0.95 { delta.signum() * 0.95 } else { delta };
+        eo1 += delta;
+        if delta.abs() < 1.0e-12 {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err(Sgp4Error::KeplerDidNotConverge);
+    }
+
+    let sineo1 = eo1.sin();
+    let coseo1 = eo1.cos();
+
+    let ecose = axnl * coseo1 + aynl * sineo1;
+    let esine = axnl * sineo1 - aynl * coseo1;
+    let el2 = axnl * axnl + aynl * aynl;
+    let pl = am * (1.0 - el2);
+    let r = am * (1.0 - ecose);
+    let rdot = gc.ke * am.sqrt() / r * esine;
+    let rfdot = gc.ke * pl.sqrt() / r;
+    let betal = (1.0 - el2).sqrt();
+    let temp = esine / (1.0 + betal);
+    let sinu = am / r * (sineo1 - aynl - axnl * temp);
+    let cosu = am / r * (coseo1 - axnl + aynl * temp);
+    let su = sinu.atan2(cosu);
+    let sin2u = 2.0 * sinu * cosu;
+    let cos2u = 1.0 - 2.0 * sinu * sinu;
+
+    let temp = 1.0 / pl;
+    let temp1 = gc.k2 * temp;
+    let temp2 = temp1 * temp;
+
+    let mrt = r * (1.0 - 1.5 * temp2 * betal * x3thm1) + 0.5 * temp1 * x1mth2 * cos2u;
+    let su = su - 0.25 * temp2 * x7thm1 * sin2u;
+    let xnode = nodep + 1.5 * temp2 * cosio * sin2u;
+    let xinc = incl + 1.5 * temp2 * cosio * sinio * cos2u;
+
+    // Short-period corrections to the radial/transverse velocity
+    // components (`rdot`/`rfdot` above), mirroring the `mrt`/`su`/`xnode`/
+    // `xinc` corrections just applied to position.
+    let mvt = rdot - n0_dp * temp1 * x1mth2 * sin2u;
+    let rvdot = rfdot + n0_dp * temp1 * (x1mth2 * cos2u + 1.5 * x3thm1);
+
+    // Orientation unit vectors of the orbital plane, in TEME: `u` is
+    // radial (towards the satellite), `v` is transverse (in the direction
+    // of motion), both in the orbital plane.
+    let sinsu = su.sin();
+    let cosu = su.cos();
+    let sinik = xinc.sin();
+    let cosik = xinc.cos();
+    let sinnok = xnode.sin();
+    let cosnok = xnode.cos();
+
+    let xmx = -sinnok * cosik;
+    let xmy = cosnok * cosik;
+
+    let ux = xmx * sinsu + cosnok * cosu;
+    let uy = xmy * sinsu + sinnok * cosu;
+    let uz = sinik * sinsu;
+
+    let vx = xmx * cosu - cosnok * sinsu;
+    let vy = xmy * cosu - sinnok * sinsu;
+    let vz = sinik * cosu;
+
+    // Position in Earth radii, scaled to kilometers; velocity in Earth
+    // radii/minute, scaled to km/s.
+    let vkmpersec = gc.xkmper / 60.0;
+    Ok(coordinates::TEME {
+        X: mrt * ux * gc.xkmper,
+        Y: mrt * uy * gc.xkmper,
+        Z: mrt * uz * gc.xkmper,
+        X_dot: (mvt * ux + rvdot * vx) * vkmpersec,
+        Y_dot: (mvt * uy + rvdot * vy) * vkmpersec,
+        Z_dot: (mvt * uz + rvdot * vz) * vkmpersec,
+    })
+}
+
+
+// ****************************************************************************
+// Deep space (SDP4).
+//
+// Lunar/solar third-body gravity and, near the 24-hour and 12-hour
+// resonances, resonance with the Earth's own gravitational field. See
+// "Models for Propagation of NORAD Element Sets", section on deep space
+// perturbations.
+
+/// $n_s = 1.19459 \times 10\^{-5}$  Solar mean motion (radians/minute).
+const ZNS: f64 = 1.19459e-5;
+
+/// Solar orbit eccentricity.
+const ZES: f64 = 0.01675;
+
+/// $n_l = 1.5835218 \times 10\^{-4}$  Lunar mean motion (radians/minute).
+const ZNL: f64 = 1.5835218e-4;
+
+/// Lunar orbit eccentricity.
+const ZEL: f64 = 0.05490;
+
+const C1SS: f64 = 2.9864797e-6;
+const C1L: f64 = 4.7968065e-7;
+const ZSINIS: f64 = 0.39785416;
+const ZCOSIS: f64 = 0.91744867;
+const ZCOSGS: f64 = 0.1945905;
+const ZSINGS: f64 = -0.98088458;
+
+// Tesseral resonance coefficients: the three 24-hour (synchronous) terms
+// are amplitude-scaled by `Qnn`; the ten-term 12-hour expansion (2:2:1/
+// 2:2:11/3:2:10/3:2:22/4:4:10/4:4:22/5:2:20/5:2:32/5:4:21/5:4:33) by the
+// roots (`ROOTnn`), with phase offsets (`Gnn`), as SPACETRACK REPORT #3's
+// DPSEC routine uses.
+const ROOT22: f64 = 1.7891679e-6;
+const ROOT32: f64 = 3.7393792e-7;
+const ROOT44: f64 = 7.3636953e-9;
+const ROOT52: f64 = 1.1428639e-7;
+const ROOT54: f64 = 2.1765803e-9;
+
+const Q22: f64 = 1.7891679e-6;
+const Q31: f64 = 2.1460748e-6;
+const Q33: f64 = 2.2123015e-7;
 
+const G22: f64 = 5.7686396;
+const G32: f64 = 0.95240898;
+const G44: f64 = 1.8014998;
+const G52: f64 = 1.0508330;
+const G54: f64 = 4.4108898;
 
-    // TODO: dummy
-    // Return coordinates
-    coordinates::TEME {
-        X: 0.0,
-        Y: 0.0,
-        Z: 0.0,
+/// Fixed synchronous-resonance phase offsets (radians).
+const FASX2: f64 = 0.13130908;
+const FASX4: f64 = 2.8843198;
+const FASX6: f64 = 0.37448087;
+
+/// Sidereal rotation rate of the Earth (radians/minute); the resonance
+/// integration tracks satellite phase against this.
+const THDT: f64 = 4.3752691e-3;
+
+/// Fixed integration step (minutes) used by the resonance integrator, and
+/// half its square (used by the second-order `xli`/`xni` update below).
+const STEPP: f64 = 720.0;
+const STEP2: f64 = 259200.0;
+
+/// Which Earth-gravity resonance (if any) a deep-space satellite's mean
+/// motion falls into, carrying that regime's tesseral term amplitudes for
+/// [`integrate_resonance`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Resonance {
+    /// Only the lunar/solar secular and periodic terms apply.
+    None,
+    /// 24-hour (geosynchronous) resonance: the synchronous 2:2:1, 3:3:1
+    /// and 3:2:1 terms, amplitude-scaled by `Q22`/`Q31`/`Q33`.
+    OneDay { del1: f64, del2: f64, del3: f64 },
+    /// 12-hour (e.g. Molniya-type) resonance: the full ten-term 2:2:1/
+    /// 2:2:11/3:2:10/3:2:22/4:4:10/4:4:22/5:2:20/5:2:32/5:4:21/5:4:33
+    /// tesseral expansion, amplitude-scaled by `ROOT22`/`ROOT32`/`ROOT44`/
+    /// `ROOT52`/`ROOT54`.
+    HalfDay {
+        d2201: f64, d2211: f64,
+        d3210: f64, d3222: f64,
+        d4410: f64, d4422: f64,
+        d5220: f64, d5232: f64,
+        d5421: f64, d5433: f64,
+        argp0: f64,
+        argpdot: f64,
+    },
+}
+
+/// The common third-body geometry sum (Section 3 of the deep-space
+/// perturbation theory), evaluated once for the Sun and once for the
+/// Moon; [`deep_space_init`] below combines the two.
+struct ThirdBodyTerms {
+    s1: f64, s2: f64, s3: f64, s4: f64, s5: f64, s6: f64, s7: f64,
+    z1: f64, z3: f64,
+    z11: f64, z13: f64,
+    z21: f64, z23: f64,
+}
+
+/// The perturbing body's (Sun or Moon) node/inclination/perigee geometry
+/// relative to the satellite's orbital plane, plus its secular amplitude
+/// `cc` (`C1SS`/`C1L`).
+struct ThirdBodyGeometry {
+    zcosg: f64, zsing: f64,
+    zcosi: f64, zsini: f64,
+    zcosh: f64, zsinh: f64,
+    cc: f64,
+}
+
+/// Satellite orbit geometry shared between the solar and lunar
+/// third-body passes.
+#[derive(Debug, Clone, Copy)]
+struct OrbitGeometry {
+    xnoi: f64,
+    em: f64, emsq: f64, betasq: f64,
+    cosim: f64, sinim: f64,
+    cosomm: f64, sinomm: f64,
+}
+
+fn third_body_terms(body: ThirdBodyGeometry, orbit: OrbitGeometry) -> ThirdBodyTerms {
+    let ThirdBodyGeometry { zcosg, zsing, zcosi, zsini, zcosh, zsinh, cc } = body;
+    let OrbitGeometry { xnoi, em, emsq, betasq, cosim, sinim, cosomm, sinomm } = orbit;
+
+    let a1 = zcosg * zcosh + zsing * zcosi * zsinh;
+    let a3 = -zsing * zcosh + zcosg * zcosi * zsinh;
+    let a7 = -zcosg * zsinh + zsing * zcosi * zcosh;
+    let a8 = zsing * zsini;
+    let a9 = zsing * zsinh + zcosg * zcosi * zcosh;
+    let a10 = zcosg * zsini;
+    let a2 = cosim * a7 + sinim * a8;
+    let a4 = cosim * a9 + sinim * a10;
+    let a5 = -sinim * a7 + cosim * a8;
+    let a6 = -sinim * a9 + cosim * a10;
+
+    let x1 = a1 * cosomm + a2 * sinomm;
+    let x2 = a3 * cosomm + a4 * sinomm;
+    let x3 = -a1 * sinomm + a2 * cosomm;
+    let x4 = -a3 * sinomm + a4 * cosomm;
+    let x5 = a5 * sinomm;
+    let x6 = a6 * sinomm;
+    let x7 = a5 * cosomm;
+    let x8 = a6 * cosomm;
+
+    let z31 = 12.0 * x1 * x1 - 3.0 * x3 * x3;
+    let z33 = 12.0 * x2 * x2 - 3.0 * x4 * x4;
+    let z1 = 3.0 * (a1 * a1 + a2 * a2) + z31 * emsq;
+    let z3 = 3.0 * (a3 * a3 + a4 * a4) + z33 * emsq;
+    let z11 = -6.0 * a1 * a5 + emsq * (-24.0 * x1 * x7 - 6.0 * x3 * x5);
+    let z13 = -6.0 * a3 * a6 + emsq * (-24.0 * x2 * x8 - 6.0 * x4 * x6);
+    let z21 = 6.0 * a2 * a5 + emsq * (24.0 * x1 * x5 - 6.0 * x3 * x7);
+    let z23 = 6.0 * a4 * a6 + emsq * (24.0 * x2 * x6 - 6.0 * x4 * x8);
+    let z1 = z1 + z1 + betasq * z31;
+    let z3 = z3 + z3 + betasq * z33;
+
+    let s3 = cc * xnoi;
+    let s2 = -0.5 * s3 / betasq.sqrt();
+    let s4 = s3 * betasq.sqrt();
+    let s1 = -15.0 * em * s4;
+    let s5 = x1 * x3 + x2 * x4;
+    let s6 = x2 * x3 + x1 * x4;
+    let s7 = x2 * x4 - x1 * x3;
+
+    ThirdBodyTerms { s1, s2, s3, s4, s5, s6, s7, z1, z3, z11, z13, z21, z23 }
+}
+
+/// Secular and long-period lunar/solar perturbation amplitudes and rates,
+/// plus the resonance regime, computed from the osculating elements at
+/// the TLE epoch.
+struct DeepSpaceInit {
+    dedt: f64, didt: f64, domdt: f64, dnodt: f64, dmdt: f64,
+    se2: f64, se3: f64, si2: f64, si3: f64, sl2: f64, sl3: f64, sl4: f64,
+    sgh2: f64, sgh3: f64, sgh4: f64, sh2: f64, sh3: f64,
+    ee2: f64, e3: f64, xi2: f64, xi3: f64, xl2: f64, xl3: f64, xl4: f64,
+    xgh2: f64, xgh3: f64, xgh4: f64, xh2: f64, xh3: f64,
+    zmos: f64, zmol: f64,
+    resonance: Resonance,
+    xlamo: f64,
+    xfact: f64,
+}
+
+fn deep_space_init(ctx: &DeepSpaceContext, ao_dp: f64) -> DeepSpaceInit {
+    let gc = ctx.gc;
+    let mode = ctx.mode;
+    let tle = ctx.tle;
+    let i0 = ctx.i0;
+    let e0 = ctx.e0;
+    let n0_dp = ctx.n0_dp;
+    let day = tle.epoch - 2415020.0;
+
+    let cosim = i0.cos();
+    let sinim = i0.sin();
+    let cosomm = tle.argp.cos();
+    let sinomm = tle.argp.sin();
+    let cnodm = tle.raan.cos();
+    let snodm = tle.raan.sin();
+    let emsq = e0 * e0;
+    let betasq = 1.0 - emsq;
+
+    let xnodce = (4.5236020 - 9.2422029e-4 * day) % (2.0 * PI);
+    let stem = xnodce.sin();
+    let ctem = xnodce.cos();
+    let zcosil = 0.91375164 - 0.03568096 * ctem;
+    let zsinil = (1.0 - zcosil * zcosil).sqrt();
+    let zsinhl = 0.089683511 * stem / zsinil;
+    let zcoshl = (1.0 - zsinhl * zsinhl).sqrt();
+    let gam = 5.8351514 + 0.0019443680 * day;
+    let zx = 0.39785416 * stem / zsinil;
+    let zy = zcoshl * ctem + 0.91744867 * zsinhl * stem;
+    let zx = zx.atan2(zy) + gam - xnodce;
+    let zcosgl = zx.cos();
+    let zsingl = zx.sin();
+
+    let orbit = OrbitGeometry { xnoi: 1.0 / n0_dp, em: e0, emsq, betasq, cosim, sinim, cosomm, sinomm };
+
+    // Solar third-body terms.
+    let solar = third_body_terms(
+        ThirdBodyGeometry { zcosg: ZCOSGS, zsing: ZSINGS, zcosi: ZCOSIS, zsini: ZSINIS, zcosh: cnodm, zsinh: snodm, cc: C1SS },
+        orbit,
+    );
+
+    // Lunar third-body terms; the geometry is the solar one rotated by the
+    // Moon's own node/inclination relative to the ecliptic.
+    let zcosh = cnodm * zcoshl + snodm * zsinhl;
+    let zsinh = snodm * zcoshl - cnodm * zsinhl;
+    let lunar = third_body_terms(
+        ThirdBodyGeometry { zcosg: zcosgl, zsing: zsingl, zcosi: zcosil, zsini: zsinil, zcosh, zsinh, cc: C1L },
+        orbit,
+    );
+
+    let zmos = (6.2565837 + 0.017201977 * day) % (2.0 * PI);
+    let zmol = (4.7199672 + 0.22997150 * day - gam) % (2.0 * PI);
+
+    // Solar secular amplitudes.
+    let se2 = 2.0 * solar.s1 * solar.s6;
+    let se3 = 2.0 * solar.s1 * solar.s7;
+    let si2 = 2.0 * solar.s2 * solar.s7;
+    let si3 = 2.0 * solar.s2 * solar.s6;
+    let sl2 = -2.0 * solar.s3 * solar.s7;
+    let sl3 = -2.0 * solar.s3 * solar.s6;
+    let sl4 = -2.0 * solar.s3 * solar.s5;
+    let sgh2 = 2.0 * solar.s4 * solar.s7;
+    let sgh3 = 2.0 * solar.s4 * solar.s6;
+    let sgh4 = -18.0 * solar.s4 * solar.s5;
+    let sh2 = -2.0 * solar.s2 * solar.s5;
+    let sh3 = -2.0 * solar.s2 * solar.s4;
+
+    // Lunar secular amplitudes.
+    let ee2 = 2.0 * lunar.s1 * lunar.s6;
+    let e3 = 2.0 * lunar.s1 * lunar.s7;
+    let xi2 = 2.0 * lunar.s2 * lunar.s7;
+    let xi3 = 2.0 * lunar.s2 * lunar.s6;
+    let xl2 = -2.0 * lunar.s3 * lunar.s7;
+    let xl3 = -2.0 * lunar.s3 * lunar.s6;
+    let xl4 = -2.0 * lunar.s3 * lunar.s5;
+    let xgh2 = 2.0 * lunar.s4 * lunar.s7;
+    let xgh3 = 2.0 * lunar.s4 * lunar.s6;
+    let xgh4 = -18.0 * lunar.s4 * lunar.s5;
+    let xh2 = -2.0 * lunar.s2 * lunar.s5;
+    let xh3 = -2.0 * lunar.s2 * lunar.s4;
+
+    // Secular rates of change (solar + lunar contributions).
+    let ses = solar.s1 * ZNS * solar.s5;
+    let sis = solar.s2 * ZNS * (solar.z11 + solar.z13);
+    let sls = -ZNS * solar.s3 * (solar.z1 + solar.z3 - 14.0 - 6.0 * emsq);
+    let sghs = solar.s4 * ZNS * (solar.z11 + solar.z13 - 6.0);
+    let mut shs = -ZNS * solar.s2 * (solar.z21 + solar.z23);
+    if !(5.2359877e-2..=PI - 5.2359877e-2).contains(&i0) {
+        shs = 0.0;
+    }
+    if sinim != 0.0 {
+        shs /= sinim;
+    }
+    let sgs = sghs - cosim * shs;
+
+    let dedt = ses + lunar.s1 * ZNL * lunar.s5;
+    let didt = sis + lunar.s2 * ZNL * (lunar.z11 + lunar.z13);
+    let dmdt = sls - ZNL * lunar.s3 * (lunar.z1 + lunar.z3 - 14.0 - 6.0 * emsq);
+    let sghl = lunar.s4 * ZNL * (lunar.z11 + lunar.z13 - 6.0);
+    let mut shll = -ZNL * lunar.s2 * (lunar.z21 + lunar.z23);
+    if !(5.2359877e-2..=PI - 5.2359877e-2).contains(&i0) {
+        shll = 0.0;
+    }
+    let mut domdt = sgs + sghl;
+    let mut dnodt = shs;
+    if sinim != 0.0 {
+        domdt -= cosim / sinim * shll;
+        dnodt += shll / sinim;
+    }
+    // Resonance detection, following the mean-motion windows used by
+    // SPACETRACK REPORT #3 to pick out the 24-hour and 12-hour cases.
+    #[derive(Clone, Copy, PartialEq)]
+    enum ResonanceRegime { None, OneDay, HalfDay }
+    let resonance_regime = if (0.0034906585..0.0052359877).contains(&n0_dp) {
+        ResonanceRegime::OneDay
+    } else if (8.26e-3..=9.24e-3).contains(&n0_dp) {
+        ResonanceRegime::HalfDay
+    } else {
+        ResonanceRegime::None
+    };
+
+    // Initial phase and the ten-term (half-day) or three-term (synchronous)
+    // tesseral resonance amplitudes `integrate_resonance` steps forward,
+    // following SPACETRACK REPORT #3's DPSEC resonance initialization.
+    let aonv = 1.0 / ao_dp;
+    let theta = time::gstime(mode, tle.epoch);
+    let xlamo = (tle.M + tle.raan + tle.raan - theta - theta) % (2.0 * PI);
+    let resonance = match resonance_regime {
+        ResonanceRegime::OneDay => {
+            // Synchronous (24-hour) terms: 2:2:1, 3:3:1, 3:2:1.
+            let g200 = 1.0 + emsq * (-2.5 + 0.8125 * emsq);
+            let g310 = 1.0 + 2.0 * emsq;
+            let g300 = 1.0 + emsq * (-6.0 + 6.60937 * emsq);
+            let f220 = 0.75 * (1.0 + cosim) * (1.0 + cosim);
+            let f311 = 0.9375 * sinim * sinim * (1.0 + 3.0 * cosim) - 0.75 * (1.0 + cosim);
+            let f330 = 1.875 * (1.0 + cosim) * (1.0 + cosim) * (1.0 + cosim);
+
+            let del1 = 3.0 * n0_dp * n0_dp * aonv * aonv;
+            let del2 = 2.0 * del1 * f220 * g200 * Q22;
+            let del3 = 3.0 * del1 * f330 * g300 * Q33 * aonv;
+            let del1 = del1 * f311 * g310 * Q31 * aonv;
+            Resonance::OneDay { del1, del2, del3 }
+        }
+        ResonanceRegime::HalfDay => {
+            // 12-hour (e.g. Molniya) terms: the full ten-term expansion.
+            let eoc = e0 * emsq;
+            let g201 = -0.306 - (e0 - 0.64) * 0.440;
+            let (g211, g310, g322, g410, g422, g520) = if e0 <= 0.65 {
+                (3.616 - 13.2470 * e0 + 16.2900 * emsq,
+                 -19.302 + 117.3900 * e0 - 228.4190 * emsq + 156.5910 * eoc,
+                 -18.9068 + 109.7927 * e0 - 214.6334 * emsq + 146.5816 * eoc,
+                 -41.122 + 242.6940 * e0 - 471.0940 * emsq + 313.9530 * eoc,
+                 -146.407 + 841.8800 * e0 - 1629.014 * emsq + 1083.4350 * eoc,
+                 -532.114 + 3017.977 * e0 - 5740.032 * emsq + 3708.2760 * eoc)
+            } else {
+                (-72.099 + 331.819 * e0 - 508.738 * emsq + 266.724 * eoc,
+                 -346.844 + 1582.851 * e0 - 2415.925 * emsq + 1246.113 * eoc,
+                 -342.585 + 1554.908 * e0 - 2366.899 * emsq + 1215.972 * eoc,
+                 -1053.725 + 4578.298 * e0 - 6888.657 * emsq + 3596.536 * eoc,
+                 -3581.690 + 16178.110 * e0 - 24462.770 * emsq + 12422.520 * eoc,
+                 if e0 > 0.715 {
+                     -5149.66 + 29936.92 * e0 - 54087.36 * emsq + 31324.56 * eoc
+                 } else {
+                     1464.74 - 4664.75 * e0 + 3763.64 * emsq
+                 })
+            };
+            let (g533, g521, g532) = if e0 < 0.7 {
+                (-919.2277 + 4988.6100 * e0 - 9064.7700 * emsq + 5542.21 * eoc,
+                 -822.7100 + 4568.6173 * e0 - 8491.4146 * emsq + 5337.524 * eoc,
+                 -853.6660 + 4690.2500 * e0 - 8624.7700 * emsq + 5341.4 * eoc)
+            } else {
+                (-37995.780 + 161616.52 * e0 - 229838.20 * emsq + 109377.94 * eoc,
+                 114.2929 - 446.9940 * e0 + 817.5250 * emsq - 420.0170 * eoc,
+                 -15764.490 + 71559.29 * e0 - 117066.24 * emsq + 63861.12 * eoc)
+            };
+
+            let sini2 = sinim * sinim;
+            let f220 = 0.75 * (1.0 + 2.0 * cosim + cosim * cosim);
+            let f221 = 1.5 * sini2;
+            let f321 = 1.875 * sinim * (1.0 - 2.0 * cosim - 3.0 * cosim * cosim);
+            let f322 = -1.875 * sinim * (1.0 + 2.0 * cosim - 3.0 * cosim * cosim);
+            let f441 = 35.0 * sini2 * f220;
+            let f442 = 39.3750 * sini2 * sini2;
+            let f522 = 9.84375 * sinim * (sini2 * (1.0 - 2.0 * cosim - 5.0 * cosim * cosim)
+                + 0.3333333 * (-2.0 + 4.0 * cosim + 6.0 * cosim * cosim));
+            let f523 = sinim * (4.92187512 * sini2 * (-2.0 - 4.0 * cosim + 10.0 * cosim * cosim)
+                + 6.56250012 * (1.0 + 2.0 * cosim - 3.0 * cosim * cosim));
+            let f542 = 29.53125 * sinim * (2.0 - 8.0 * cosim + cosim * cosim * (-12.0 + 8.0 * cosim + 10.0 * cosim * cosim));
+            let f543 = 29.53125 * sinim * (-2.0 - 8.0 * cosim + cosim * cosim * (12.0 + 8.0 * cosim - 10.0 * cosim * cosim));
+
+            let xno2 = n0_dp * n0_dp;
+            let ainv2 = aonv * aonv;
+            let temp1 = 3.0 * xno2 * ainv2;
+            let temp = temp1 * ROOT22;
+            let d2201 = temp * f220 * g201;
+            let d2211 = temp * f221 * g211;
+            let temp1 = temp1 * aonv;
+            let temp = temp1 * ROOT32;
+            let d3210 = temp * f321 * g310;
+            let d3222 = temp * f322 * g322;
+            let temp1 = temp1 * aonv;
+            let temp = 2.0 * temp1 * ROOT44;
+            let d4410 = temp * f441 * g410;
+            let d4422 = temp * f442 * g422;
+            let temp1 = temp1 * aonv;
+            let temp = temp1 * ROOT52;
+            let d5220 = temp * f522 * g520;
+            let d5232 = temp * f523 * g532;
+            let temp = 2.0 * temp1 * ROOT54;
+            let d5421 = temp * f542 * g521;
+            let d5433 = temp * f543 * g533;
+            Resonance::HalfDay {
+                d2201, d2211, d3210, d3222, d4410, d4422, d5220, d5232, d5421, d5433,
+                argp0: ctx.tle.argp,
+                argpdot: ctx.argpdot,
+            }
+        }
+        ResonanceRegime::None => Resonance::None,
+    };
+    let xfact = mdot_base(gc, n0_dp, i0) + dmdt + 2.0 * (nodedot_base(gc, n0_dp, i0) + dnodt - THDT) - n0_dp;
+
+    DeepSpaceInit {
+        dedt, didt, domdt, dnodt, dmdt,
+        se2, se3, si2, si3, sl2, sl3, sl4, sgh2, sgh3, sgh4, sh2, sh3,
+        ee2, e3, xi2, xi3, xl2, xl3, xl4, xgh2, xgh3, xgh4, xh2, xh3,
+        zmos, zmol,
+        resonance, xlamo, xfact,
     }
 }
 
+/// The secular mean-motion/node rates used only to seed the resonance
+/// phase rate in [`deep_space_init`] — a coarse version of Section 4's
+/// `mdot`/`nodedot` that doesn't need the drag terms.
+fn mdot_base(gc: GravityConstants, n0_dp: f64, i0: f64) -> f64 {
+    n0_dp + 0.75 * gc.k2 * n0_dp * (3.0 * i0.cos() * i0.cos() - 1.0) / (n0_dp * n0_dp)
+}
+
+fn nodedot_base(gc: GravityConstants, n0_dp: f64, i0: f64) -> f64 {
+    -1.5 * gc.k2 * n0_dp * i0.cos() / (n0_dp * n0_dp)
+}
+
+/// The mean-motion/longitude perturbation rates (`xndt`, `xldot`, `xnddt`)
+/// at the current integration state, for whichever tesseral terms
+/// `resonance` carries. Shared between `integrate_resonance`'s stepping
+/// loop and its final partial-step interpolation.
+fn resonance_rates(resonance: Resonance, atime: f64, xli: f64, xni: f64, xfact: f64) -> (f64, f64, f64) {
+    let xldot = xni + xfact;
+    let (xndt, xnddt) = match resonance {
+        Resonance::None => (0.0, 0.0),
+        Resonance::OneDay { del1, del2, del3 } => {
+            let xndt = del1 * (xli - FASX2).sin() + del2 * (2.0 * (xli - FASX4)).sin() + del3 * (3.0 * (xli - FASX6)).sin();
+            let xnddt = del1 * (xli - FASX2).cos() + 2.0 * del2 * (2.0 * (xli - FASX4)).cos() + 3.0 * del3 * (3.0 * (xli - FASX6)).cos();
+            (xndt, xnddt * xldot)
+        }
+        Resonance::HalfDay { d2201, d2211, d3210, d3222, d4410, d4422, d5220, d5232, d5421, d5433, argp0, argpdot } => {
+            let xomi = argp0 + argpdot * atime;
+            let x2omi = xomi + xomi;
+            let x2li = xli + xli;
+            let xndt = d2201 * (x2omi + xli - G22).sin() + d2211 * (xli - G22).sin()
+                + d3210 * (xomi + xli - G32).sin() + d3222 * (-xomi + xli - G32).sin()
+                + d4410 * (x2omi + x2li - G44).sin() + d4422 * (x2li - G44).sin()
+                + d5220 * (xomi + xli - G52).sin() + d5232 * (-xomi + xli - G52).sin()
+                + d5421 * (xomi + x2li - G54).sin() + d5433 * (-xomi + x2li - G54).sin();
+            let xnddt = d2201 * (x2omi + xli - G22).cos() + d2211 * (xli - G22).cos()
+                + d3210 * (xomi + xli - G32).cos() + d3222 * (-xomi + xli - G32).cos()
+                + 2.0 * (d4410 * (x2omi + x2li - G44).cos() + d4422 * (x2li - G44).cos()
+                    + d5421 * (xomi + x2li - G54).cos() + d5433 * (-xomi + x2li - G54).cos())
+                + d5220 * (xomi + xli - G52).cos() + d5232 * (-xomi + xli - G52).cos();
+            (xndt, xnddt * xldot)
+        }
+    };
+    (xndt, xldot, xnddt)
+}
+
+/// Integrate the tesseral resonance contribution to mean anomaly from the
+/// TLE epoch (`t = 0`) to `t` minutes, stepping by a fixed 720 minutes
+/// (forward or backward as needed) as SPACETRACK REPORT #3's `DSPACE`
+/// describes, then interpolating the final partial step.
+///
+/// The mean-anomaly correction is the net drift of the resonance longitude
+/// `xli` away from its unperturbed value `xlamo + xfact * t`; `xni` (the
+/// resonance's own mean-motion-like rate, initialized from `n0_dp`) drives
+/// `xli`'s step in the same second-order predictor SPACETRACK Report #3
+/// uses for `atime`/`xli`/`xni` themselves.
+fn integrate_resonance(resonance: Resonance, n0_dp: f64, xlamo: f64, xfact: f64, t: f64) -> f64 {
+    if resonance == Resonance::None {
+        return 0.0;
+    }
+
+    let mut atime = 0.0;
+    let mut xli = xlamo;
+    let mut xni = n0_dp;
+
+    let dir = if t >= 0.0 { 1.0 } else { -1.0 };
+    let delt = STEPP * dir;
+
+    while (t - atime).abs() >= STEPP {
+        let (xndt, xldot, xnddt) = resonance_rates(resonance, atime, xli, xni, xfact);
+        atime += delt;
+        xli += xldot * delt + xndt * STEP2;
+        xni += xndt * delt + xnddt * STEP2;
+    }
+
+    // Final partial-step interpolation up to the requested time.
+    let (xndt, xldot, _) = resonance_rates(resonance, atime, xli, xni, xfact);
+    let ft = t - atime;
+    let xl = xli + xldot * ft + xndt * ft * ft * 0.5;
+
+    xl - xlamo - xfact * t
+}
+
+/// Apply the deep-space lunar/solar secular and periodic corrections (and,
+/// for resonant orbits, the integrated Earth-resonance term) to the
+/// drag-corrected mean elements, ready for the shared `periodics` step.
+fn sdp4_corrections(ctx: &DeepSpaceContext, t: f64, elements: MeanElements) -> MeanElements {
+    let MeanElements { mut em, mut mp, mut argpp, mut nodep, incl: _ } = elements;
+
+    let ao_dp = {
+        let cos2_i0 = ctx.i0.cos() * ctx.i0.cos();
+        let e02 = ctx.e0 * ctx.e0;
+        let a1 = (ctx.gc.ke / ctx.tle.mean_motion).powf(2.0 / 3.0);
+        let d1 = (3.0 * ctx.gc.k2 * (3.0 * cos2_i0 - 1.0)) / (2.0 * a1 * a1 * (1.0 - e02).powf(1.5));
+        let a0 = a1 * (1.0 - (d1 / 3.0) - (d1 * d1) - (134.0 * d1 * d1 * d1 / 81.0));
+        let d0 = (3.0 * ctx.gc.k2 * (3.0 * cos2_i0 - 1.0)) / (2.0 * a0 * a0 * (1.0 - e02).powf(1.5));
+        a0 / (1.0 - d0)
+    };
+
+    let init = deep_space_init(ctx, ao_dp);
+
+    // Secular lunar/solar rates.
+    em += init.dedt * t;
+    let mut incl = ctx.i0 + init.didt * t;
+    argpp += init.domdt * t;
+    nodep += init.dnodt * t;
+    mp += init.dmdt * t;
+
+    if init.resonance != Resonance::None {
+        mp += integrate_resonance(init.resonance, ctx.n0_dp, init.xlamo, init.xfact, t);
+    }
+
+    // Long-period periodics (`dpper`).
+    let zm = init.zmos + ZNS * t;
+    let zf = zm + 2.0 * ZES * zm.sin();
+    let sinzf = zf.sin();
+    let coszf = zf.cos();
+    let f2 = 0.5 * sinzf * sinzf - 0.25;
+    let f3 = -0.5 * sinzf * coszf;
+    let ses = init.se2 * f2 + init.se3 * f3;
+    let sis = init.si2 * f2 + init.si3 * f3;
+    let sls = init.sl2 * f2 + init.sl3 * f3 + init.sl4 * sinzf;
+    let sghs = init.sgh2 * f2 + init.sgh3 * f3 + init.sgh4 * sinzf;
+    let shs = init.sh2 * f2 + init.sh3 * f3;
+
+    let zm = init.zmol + ZNL * t;
+    let zf = zm + 2.0 * ZEL * zm.sin();
+    let sinzf = zf.sin();
+    let coszf = zf.cos();
+    let f2 = 0.5 * sinzf * sinzf - 0.25;
+    let f3 = -0.5 * sinzf * coszf;
+    let sel = init.ee2 * f2 + init.e3 * f3;
+    let sil = init.xi2 * f2 + init.xi3 * f3;
+    let sll = init.xl2 * f2 + init.xl3 * f3 + init.xl4 * sinzf;
+    let sghl = init.xgh2 * f2 + init.xgh3 * f3 + init.xgh4 * sinzf;
+    let shll = init.xh2 * f2 + init.xh3 * f3;
+
+    let pe = ses + sel;
+    let pinc = sis + sil;
+    let pl = sls + sll;
+    let mut pgh = sghs + sghl;
+    let mut ph = shs + shll;
+
+    em += pe;
+    incl += pinc;
+    mp += pl;
+
+    let sinip = incl.sin();
+    if incl >= 0.2 {
+        ph /= sinip;
+        pgh -= incl.cos() * ph;
+        argpp += pgh;
+        nodep += ph;
+    } else {
+        // Near-equatorial orbits: apply the node/perigee periodics
+        // directly, without the 1/sin(i) resonance-avoiding split that
+        // `dpper` otherwise uses.
+        argpp += pgh;
+        nodep += ph;
+    }
+
+    MeanElements { em, mp, argpp, nodep, incl }
+}
+
 #[cfg(test)]
 mod tests {
-
     use tle::load_from_str;
-    use coordinates::TEME;
     use super::propagate;
 
+    /// Reference position vectors (km) are only good to a handful of
+    /// significant digits in the original report, so compare with a loose
+    /// (but not meaninglessly loose) tolerance rather than bit-for-bit.
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 0.01, "expected {}, got {}", expected, actual);
+    }
+
+    /// Reference velocity (km/s) is a much smaller quantity than position,
+    /// so it needs a correspondingly tighter tolerance.
+    fn assert_close_velocity(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1.0e-4, "expected {}, got {}", expected, actual);
+    }
+
     #[test]
     fn spacetrack_report_3_sgp4_test_case() {
         // This testcase is from "SPACETRACK REPORT NO. 3, Models for
@@ -199,12 +1089,107 @@ mod tests {
         );
 
         // Compute
-        let result0 = propagate(tle, 0.0);
-        assert_eq!(result0, TEME {
-            X: 0.0,
-            Y: 0.0,
-            Z: 0.0,
-        });
+        let result0 = propagate(tle, 0.0).unwrap();
+        assert_close(result0.X, 2328.97);
+        assert_close(result0.Y, -5995.22);
+        assert_close(result0.Z, 1719.97);
+        assert_close_velocity(result0.X_dot, 2.91207);
+        assert_close_velocity(result0.Y_dot, -0.98342);
+        assert_close_velocity(result0.Z_dot, -7.09082);
+    }
+
+    #[test]
+    fn deep_space_geosynchronous_resonance_test_case() {
+        // A synthetic geosynchronous (1.0027 rev/day, 24-hour 1:1 resonant)
+        // TLE, constructed to exercise the SDP4 path: `deep_space_init`'s
+        // solar/lunar secular terms, the `OneDay` resonance branch, and
+        // `integrate_resonance`'s 720-minute stepping (only exercised for
+        // |t| large enough to take at least one full step). There's no
+        // published reference vector for this exact TLE, so the expected
+        // values below were captured from this code after implementing the
+        // full del1/del2/del3 synchronous-resonance expansion (in place of
+        // a single dominant term) -- this guards against regressions in
+        // the resonance/third-body terms, not against the underlying
+        // theory.
+        let tle = load_from_str(
+            "Test",
+            "1 88889U          00010.00000000  .00000000  00000-0  00000-0 0     8",
+            "2 88889   0.0500  75.0000 0002000  15.0000 320.0000  1.00270000   105",
+        );
+
+        let result = propagate(tle, 1440.0).unwrap();
+        assert_close(result.X, 26001.86);
+        assert_close(result.Y, 33184.42);
+        assert_close(result.Z, -14.80);
+        assert_close_velocity(result.X_dot, -2.42084);
+        assert_close_velocity(result.Y_dot, 1.89638);
+        assert_close_velocity(result.Z_dot, 0.00253);
+    }
 
+    #[test]
+    fn deep_space_half_day_resonance_test_case() {
+        // A synthetic Molniya-type (2.0056 rev/day, 12-hour 2:1 resonant,
+        // highly eccentric) TLE, constructed to exercise the `HalfDay`
+        // resonance branch: the ten-term 2:2:1/2:2:11/3:2:10/3:2:22/
+        // 4:4:10/4:4:22/5:2:20/5:2:32/5:4:21/5:4:33 tesseral expansion in
+        // `deep_space_init` and `integrate_resonance`. There's no
+        // published reference vector for this exact TLE, so the expected
+        // values below were captured from this code after implementing
+        // the full ten-term expansion (in place of a single dominant
+        // term) -- this guards against regressions in the resonance
+        // terms, not against the underlying theory.
+        let tle = load_from_str(
+            "Test",
+            "1 88891U          00010.00000000  .00000000  00000-0  00000-0 0     8",
+            "2 88891  63.4000 200.0000 7000000 270.0000  25.0000  2.00560000    10",
+        );
+
+        let result = propagate(tle, 1440.0).unwrap();
+        assert_close(result.X, -15099.04);
+        assert_close(result.Y, -9104.67);
+        assert_close(result.Z, 6808.02);
+        assert_close_velocity(result.X_dot, -0.75523);
+        assert_close_velocity(result.Y_dot, -2.63549);
+        assert_close_velocity(result.Z_dot, 4.43366);
+    }
+
+    #[test]
+    fn low_perigee_drag_constant_test_case() {
+        // Two synthetic low-perigee TLEs built from the same mean motion
+        // and inclination, differing only in eccentricity so one lands in
+        // each branch of the perigee-dependent `s`/(qₒ-s)⁴ recomputation:
+        // perigee ~154 km (the `perigee < 156` branch) and perigee ~95 km
+        // (additionally below the `perigee < 98` floor-clamp). There's no
+        // published reference ephemeris for these synthetic orbits, so the
+        // expected values below were captured from this code after fixing
+        // the branch order and threading the recomputed (qₒ-s)⁴ through
+        // `C2`..`C5`/`xmcof` -- this guards against the drag constant
+        // silently reverting to the gravity model's default for low-perigee
+        // satellites, not against the underlying theory.
+        let perigee_154 = load_from_str(
+            "Test",
+            "1 90000U 00001A   00001.00000000  .00050000  00000-0  10000-2 0  9991",
+            "2 90000  50.0000 100.0000 0100000 090.0000 270.0000 16.20000000    10",
+        );
+        let result = propagate(perigee_154, 360.0).unwrap();
+        assert_close(result.X, -2290.12);
+        assert_close(result.Y, 5955.69);
+        assert_close(result.Z, 1546.88);
+        assert_close_velocity(result.X_dot, -4.29461);
+        assert_close_velocity(result.Y_dot, -3.21497);
+        assert_close_velocity(result.Z_dot, 5.67369);
+
+        let perigee_95 = load_from_str(
+            "Test",
+            "1 90000U 00001A   00001.00000000  .00050000  00000-0  10000-2 0  9991",
+            "2 90000  50.0000 100.0000 0190000 090.0000 270.0000 16.20000000    10",
+        );
+        let result = propagate(perigee_95, 360.0).unwrap();
+        assert_close(result.X, -3745.20);
+        assert_close(result.Y, 3375.65);
+        assert_close(result.Z, 3755.74);
+        assert_close_velocity(result.X_dot, -2.11844);
+        assert_close_velocity(result.Y_dot, -6.74953);
+        assert_close_velocity(result.Z_dot, 3.78777);
     }
 }
@@ -0,0 +1,139 @@
+//! # Two-Line Element sets
+//!
+//! Parsing of NORAD [Two-Line Element
+//! sets](https://en.wikipedia.org/wiki/Two-line_element_set) (TLEs) into the
+//! mean orbital elements consumed by [`propagate`](../fn.propagate.html).
+
+use std::f64::consts::PI;
+
+/// Minutes per day, used to convert the TLE mean motion (revolutions/day)
+/// into radians/minute.
+const MINUTES_PER_DAY: f64 = 1440.0;
+
+
+/// A NORAD Two-Line Element set, reduced to the mean orbital elements the
+/// SGP4/SDP4 models need. Angles are stored in radians and the mean motion
+/// and its derivatives in radians/minute (and higher powers thereof) so
+/// they can be fed straight into [`propagate`](../fn.propagate.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TLE {
+    /// Satellite name, exactly as passed to [`load_from_str`] (typically
+    /// the optional title line); it is not derived from line 1.
+    pub name: String,
+
+    /// Epoch of the elements, as a full Julian day.
+    pub epoch: f64,
+
+    /// $\dot{n}/2$  First time derivative of the mean motion (radians/minute²).
+    pub ndot: f64,
+
+    /// $\ddot{n}/6$  Second time derivative of the mean motion (radians/minute³).
+    pub nddot: f64,
+
+    /// $B^*$  The SGP4-style drag term.
+    pub bstar: f64,
+
+    /// $i_0$  Inclination (radians).
+    pub i: f64,
+
+    /// $\Omega_0$  Right ascension of the ascending node (radians).
+    pub raan: f64,
+
+    /// $e_0$  Eccentricity.
+    pub e: f64,
+
+    /// $\omega_0$  Argument of perigee (radians).
+    pub argp: f64,
+
+    /// $M_0$  Mean anomaly (radians).
+    pub M: f64,
+
+    /// $n_0$  Mean motion (radians/minute).
+    pub mean_motion: f64,
+}
+
+
+/// Parse a two-line element set into a [`TLE`].
+///
+/// `name` is used verbatim as the satellite name (callers typically pass
+/// the title line that precedes `line1`/`line2` in a `.tle` file).
+/// `line1` and `line2` must be the 69-column lines as defined by
+/// SPACETRACK REPORT NO. 3.
+pub fn load_from_str(name: &str, line1: &str, line2: &str) -> TLE {
+
+    // -- Line 1 -------------------------------------------------------
+
+    let epoch_year: i32 = line1[18..20].trim().parse().expect("invalid epoch year");
+    let epoch_day: f64 = line1[20..32].trim().parse().expect("invalid epoch day");
+    let epoch = epoch_to_julian_day(epoch_year, epoch_day);
+
+    let ndot: f64 = line1[33..43].trim().parse().expect("invalid ndot");
+    let ndot = ndot * 2.0 * PI / (MINUTES_PER_DAY * MINUTES_PER_DAY);
+
+    let nddot = parse_assumed_decimal(&line1[44..52]);
+    let nddot = nddot * 2.0 * PI / (MINUTES_PER_DAY * MINUTES_PER_DAY * MINUTES_PER_DAY);
+
+    let bstar = parse_assumed_decimal(&line1[53..61]);
+
+    // -- Line 2 -------------------------------------------------------
+
+    let i: f64 = line2[8..16].trim().parse().expect("invalid inclination");
+    let raan: f64 = line2[17..25].trim().parse().expect("invalid raan");
+    let e: f64 = format!("0.{}", line2[26..33].trim()).parse().expect("invalid eccentricity");
+    let argp: f64 = line2[34..42].trim().parse().expect("invalid argument of perigee");
+    let M: f64 = line2[43..51].trim().parse().expect("invalid mean anomaly");
+    let mean_motion: f64 = line2[52..63].trim().parse().expect("invalid mean motion");
+
+    TLE {
+        name: name.to_string(),
+        epoch,
+        ndot,
+        nddot,
+        bstar,
+        i: i.to_radians(),
+        raan: raan.to_radians(),
+        e,
+        argp: argp.to_radians(),
+        M: M.to_radians(),
+        mean_motion: mean_motion * 2.0 * PI / MINUTES_PER_DAY,
+    }
+}
+
+
+/// TLE decimal fields such as `nddot`/`bstar` are given as an "assumed
+/// decimal point" mantissa plus a power-of-ten exponent, e.g. ` 66816-4`
+/// means $0.66816 \times 10\^{-4}$.
+fn parse_assumed_decimal(field: &str) -> f64 {
+    let field = field.trim();
+    if field.is_empty() {
+        return 0.0;
+    }
+
+    let (sign, rest) = match field.chars().next().unwrap() {
+        '-' => (-1.0, &field[1..]),
+        '+' => (1.0, &field[1..]),
+        _ => (1.0, field),
+    };
+
+    let (mantissa, exponent) = rest.split_at(rest.len() - 2);
+    let mantissa: f64 = format!("0.{}", mantissa).parse().expect("invalid mantissa");
+    let exponent: i32 = exponent.parse().expect("invalid exponent");
+
+    sign * mantissa * 10f64.powi(exponent)
+}
+
+
+/// Convert a TLE epoch (two-digit year + fractional day of year, using the
+/// usual NORAD convention that years `57..=99` are 1957-1999 and `00..=56`
+/// are 2000-2056) to a Julian day.
+fn epoch_to_julian_day(year: i32, day: f64) -> f64 {
+    let year = if year < 57 { year + 2000 } else { year + 1900 };
+
+    // Julian day of January 0.0 of `year`.
+    let jan0 = 367.0 * year as f64
+        - (7.0 * year as f64 / 4.0).floor()
+        + (275.0_f64 / 9.0).floor()
+        + 1721013.5;
+
+    jan0 + day
+}